@@ -0,0 +1,53 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry},
+    theme,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::playlist::RepeatMode;
+
+pub const CONFIG_VERSION: u64 = 1;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AppTheme {
+    Dark,
+    Light,
+    #[default]
+    System,
+}
+
+impl AppTheme {
+    pub fn theme(&self) -> theme::Theme {
+        match self {
+            Self::Dark => theme::Theme::dark(),
+            Self::Light => theme::Theme::light(),
+            Self::System => theme::system_preference(),
+        }
+    }
+}
+
+/// Application-wide settings, persisted via `cosmic_config`.
+#[derive(Clone, Debug, PartialEq, CosmicConfigEntry, Serialize, Deserialize)]
+pub struct Config {
+    pub app_theme: AppTheme,
+    /// Last-selected repeat mode, restored on launch.
+    pub repeat_mode: RepeatMode,
+    /// Whether the queue was shuffled, restored on launch.
+    pub shuffle: bool,
+    /// Last non-muted volume level, in `0.0..=1.0`, restored on launch.
+    pub volume: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            app_theme: AppTheme::default(),
+            repeat_mode: RepeatMode::default(),
+            shuffle: false,
+            volume: 1.0,
+        }
+    }
+}
@@ -14,13 +14,28 @@ use cosmic::{
     widget::{self, Column, Row, Slider},
     Application, ApplicationExt, Element,
 };
+use gst::prelude::*;
+use gst_pbutils::prelude::*;
 use iced_video_player::{Video, VideoPlayer};
-use std::{any::TypeId, collections::HashMap, time::Duration};
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 use crate::{
     config::{Config, CONFIG_VERSION},
+    hls::{self, BandwidthEstimator, Variant, VariantSelection},
     key_bind::{key_binds, KeyBind},
     localize,
+    metadata::{self, Metadata},
+    mpris,
+    playlist::{self, Playlist, PlaylistEntry, RepeatMode},
+    thumbnail::{self, Thumbnail, ThumbnailGenerator},
 };
 
 /// Runs application with these settings
@@ -51,31 +66,62 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     settings = settings.theme(config.app_theme.theme());
     settings = settings.size_limits(Limits::NONE.min_width(360.0).min_height(180.0));
 
-            let url = url::Url::from_file_path(
-                std::env::args().nth(1).unwrap()
-            )
-            .unwrap();
+    let entries = playlist::entries_from_args(std::env::args().skip(1));
+    assert!(!entries.is_empty(), "no playable media given");
     let flags = Flags {
         config_handler,
         config,
-        url,
+        entries,
     };
     cosmic::app::run::<App>(settings, flags)?;
 
     Ok(())
 }
 
+/// Probes `url` for a video stream via GStreamer's stream-info
+/// [`Discoverer`](gst_pbutils::Discoverer), so [`App::is_audio_only`] isn't
+/// left inferring it from `Video::width`, which reads `0` for real video
+/// too until its first frame prerolls. Defaults to `true` (assume video) on
+/// any probe failure, so we'd rather attempt the video widget than hide a
+/// stream we failed to inspect.
+async fn has_video_track(url: url::Url) -> bool {
+    tokio::task::spawn_blocking(move || {
+        let Ok(discoverer) = gst_pbutils::Discoverer::new(gst::ClockTime::from_seconds(5)) else {
+            return true;
+        };
+        discoverer
+            .discover_uri(url.as_str())
+            .map(|info| !info.video_streams().is_empty())
+            .unwrap_or(true)
+    })
+    .await
+    .unwrap_or(true)
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Action {
     SeekBackward,
     SeekForward,
+    Next,
+    Previous,
+    VolumeUp,
+    VolumeDown,
+    ToggleMute,
 }
 
+/// How much `VolumeUp`/`VolumeDown` change the volume by.
+const VOLUME_STEP: f64 = 0.05;
+
 impl Action {
     pub fn message(&self) -> Message {
         match self {
             Self::SeekBackward => Message::SeekRelative(-10.0),
             Self::SeekForward => Message::SeekRelative(10.0),
+            Self::Next => Message::Next,
+            Self::Previous => Message::Previous,
+            Self::VolumeUp => Message::SetVolume(VOLUME_STEP),
+            Self::VolumeDown => Message::SetVolume(-VOLUME_STEP),
+            Self::ToggleMute => Message::ToggleMute,
         }
     }
 }
@@ -84,7 +130,7 @@ impl Action {
 pub struct Flags {
     config_handler: Option<cosmic_config::Config>,
     config: Config,
-    url: url::Url,
+    entries: Vec<PlaylistEntry>,
 }
 
 /// Messages that are used specifically by our [`App`].
@@ -97,9 +143,27 @@ pub enum Message {
     Seek(f64),
     SeekRelative(f64),
     SeekRelease,
+    SeekAbsolute(f64),
+    Stop,
     EndOfStream,
     NewFrame,
     SystemThemeModeChange(cosmic_theme::ThemeMode),
+    VariantsLoaded(Vec<Variant>),
+    SelectVariant(usize),
+    HoverSeek(f64),
+    HoverEnd,
+    ThumbnailReady(f64, Option<Thumbnail>),
+    Next,
+    Previous,
+    PlayIndex(usize),
+    ToggleShuffle,
+    CycleRepeat,
+    MetadataLoaded(Option<Metadata>),
+    SetVolume(f64),
+    SetVolumeLevel(f64),
+    ToggleMute,
+    SetLoopStatus(mpris::LoopStatus),
+    VideoTrackProbed(bool),
 }
 
 /// The [`App`] stores application-specific state.
@@ -110,17 +174,409 @@ pub struct App {
     video: Video,
     position: f64,
     dragging: bool,
+    /// Variants advertised by the master playlist, empty for non-HLS media.
+    variants: Vec<Variant>,
+    /// `Auto` lets `bandwidth` drive selection; `Fixed` pins a quality.
+    variant_selection: VariantSelection,
+    current_variant: Option<usize>,
+    bandwidth: BandwidthEstimator,
+    /// Bytes counted off the active pipeline's `hlsdemux` src pad(s) since
+    /// the last sample; see `wire_bandwidth_probe`.
+    bandwidth_bytes: Arc<AtomicU64>,
+    /// Separate from `video`: generates still frames for the hover preview
+    /// without disturbing main playback.
+    thumbnails: Arc<Mutex<ThumbnailGenerator>>,
+    /// Timestamp currently hovered on the slider, and its thumbnail once
+    /// generation completes.
+    hover: Option<(f64, Option<Thumbnail>)>,
+    playlist: Playlist,
+    /// Tags and cover art for the current entry, loaded for every entry and
+    /// shown in the now-playing panel when it has no video stream. `None`
+    /// while loading, and when no tags could be read (see [`metadata::load`]).
+    metadata: Option<Metadata>,
+    /// Whether the current entry has a video stream, per [`has_video_track`].
+    /// Starts `true` (assume video) until the probe completes, so a real
+    /// video's not-yet-prerolled first frame never flashes the now-playing
+    /// panel.
+    has_video: bool,
+    /// Last non-muted volume level, in `0.0..=1.0`.
+    volume: f64,
+    muted: bool,
+    /// Wall-clock instant of the last sample folded into `bandwidth`, and
+    /// how long the playback position has sat still since then. Used to
+    /// turn `NewFrame` ticks into the periodic throughput samples and
+    /// starvation signals `BandwidthEstimator` needs (see
+    /// `reevaluate_bandwidth`).
+    last_bandwidth_sample: Instant,
+    stalled_since: Option<Instant>,
 }
 
+/// Only switch up to a pricier variant once it fits comfortably under the
+/// estimate, leaving headroom for jitter.
+const SAFETY_FACTOR: f64 = 0.8;
+
 impl App {
+    /// How often `reevaluate_bandwidth` folds a new throughput sample into
+    /// `bandwidth` and re-checks `variant_selection`.
+    const BANDWIDTH_SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+    /// How long the reported position can sit still, while playing, before
+    /// it's treated as a stalled/starved download rather than jitter.
+    const STARVATION_TIMEOUT: Duration = Duration::from_secs(1);
+
     fn update_config(&mut self) -> Command<Message> {
         cosmic::app::command::set_theme(self.flags.config.app_theme.theme())
     }
 
     fn update_title(&mut self) -> Command<Message> {
-        let title = "COSMIC Media Player";
-        self.set_header_title(title.to_string());
-        self.set_window_title(title.to_string())
+        let title = match (&self.metadata, self.playlist.current()) {
+            (Some(metadata), Some(entry)) => metadata.display_title(&entry.url),
+            (None, Some(entry)) => entry.title(),
+            (_, None) => "COSMIC Media Player".to_string(),
+        };
+        self.set_header_title(title.clone());
+        self.set_window_title(title)
+    }
+
+    /// Whether the current media has no video stream, and should show the
+    /// now-playing metadata panel instead of the video widget.
+    fn is_audio_only(&self) -> bool {
+        !self.has_video
+    }
+
+    /// The variant that should currently be playing, per `variant_selection`.
+    fn active_variant(&mut self) -> Option<&Variant> {
+        match self.variant_selection {
+            VariantSelection::Fixed(index) => self.variants.get(index),
+            VariantSelection::Auto => {
+                let current = self.current_variant.and_then(|i| self.variants.get(i));
+                self.bandwidth.select(&self.variants, current, SAFETY_FACTOR)
+            }
+        }
+    }
+
+    /// Re-points the underlying [`Video`] at `variant`'s media playlist,
+    /// preserving playback position.
+    fn load_variant(&mut self, variant: &Variant) {
+        self.current_variant = self.variants.iter().position(|v| v == variant);
+        if self.video.uri() == variant.uri.as_str() {
+            return;
+        }
+        let position = self.position;
+        let paused = self.video.paused();
+        if let Ok(video) = Video::new(&variant.uri) {
+            self.video = video;
+            self.video
+                .seek(Duration::from_secs_f64(position), false)
+                .ok();
+            self.video.set_paused(paused);
+            self.video
+                .set_volume(if self.muted { 0.0 } else { self.volume });
+            self.wire_bandwidth_probe();
+        }
+    }
+
+    /// Counts bytes flowing out of the pipeline's HLS fragment demuxer —
+    /// the real throughput signal `reevaluate_bandwidth` folds into
+    /// `bandwidth`, replacing a counter each time `self.video`'s pipeline
+    /// is (re)built.
+    ///
+    /// `hlsdemux` is auto-plugged deep inside `Video`'s pipeline and only
+    /// creates its src pad(s) once the fragment stream actually starts, so
+    /// we listen for the pipeline's `deep-element-added` signal to catch
+    /// it wherever it's plugged in, then probe every buffer crossing its
+    /// output. Counting demuxed bytes is a closer proxy for network
+    /// throughput than hlsdemux's own advertised `BANDWIDTH` value, which
+    /// is just the figure the server *claims* for the variant.
+    fn wire_bandwidth_probe(&mut self) {
+        let bytes = Arc::new(AtomicU64::new(0));
+        self.bandwidth_bytes = bytes.clone();
+
+        self.video
+            .pipeline()
+            .connect_deep_element_added(move |_pipeline, _bin, element| {
+                let is_hlsdemux = element
+                    .factory()
+                    .map(|factory| factory.name().contains("hlsdemux"))
+                    .unwrap_or(false);
+                if !is_hlsdemux {
+                    return;
+                }
+                let bytes = bytes.clone();
+                element.connect_pad_added(move |_element, pad| {
+                    let bytes = bytes.clone();
+                    let _ = pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, info| {
+                        if let Some(buffer) = info.buffer() {
+                            bytes.fetch_add(buffer.size() as u64, Ordering::Relaxed);
+                        }
+                        gst::PadProbeReturn::Ok
+                    });
+                });
+            });
+    }
+
+    /// Folds playback health since the last `NewFrame` into `bandwidth`
+    /// and, for `Auto` selection, re-evaluates which variant should be
+    /// playing.
+    ///
+    /// Throughput samples come from `bandwidth_bytes`, drained every
+    /// `BANDWIDTH_SAMPLE_INTERVAL` (so rapid `NewFrame` ticks don't flood
+    /// the EWMA); a position frozen for `STARVATION_TIMEOUT` despite new
+    /// frames ticking in is treated as starvation, bypassing that interval.
+    fn reevaluate_bandwidth(&mut self, previous_position: f64) {
+        if self.variants.is_empty() || self.video.paused() {
+            self.stalled_since = None;
+            return;
+        }
+
+        let now = Instant::now();
+        if self.position <= previous_position {
+            if self.stalled_since.get_or_insert(now).elapsed() < Self::STARVATION_TIMEOUT {
+                return;
+            }
+            self.bandwidth.note_starvation();
+            self.bandwidth_bytes.store(0, Ordering::Relaxed);
+            self.stalled_since = None;
+        } else {
+            self.stalled_since = None;
+            let elapsed = now.duration_since(self.last_bandwidth_sample);
+            if elapsed < Self::BANDWIDTH_SAMPLE_INTERVAL {
+                return;
+            }
+            let bytes = self.bandwidth_bytes.swap(0, Ordering::Relaxed);
+            if bytes > 0 {
+                self.bandwidth.record_segment(bytes, elapsed);
+            }
+        }
+        self.last_bandwidth_sample = now;
+
+        if let Some(variant) = self.active_variant().cloned() {
+            self.load_variant(&variant);
+        }
+    }
+
+    /// Persists the repeat/shuffle choice and volume level, if a config
+    /// handler is available.
+    fn save_config(&mut self) -> Command<Message> {
+        if let Some(config_handler) = self.flags.config_handler.clone() {
+            if let Err(err) = self
+                .flags
+                .config
+                .set_repeat_mode(&config_handler, self.flags.config.repeat_mode)
+            {
+                log::error!("failed to save repeat mode: {}", err);
+            }
+            if let Err(err) = self
+                .flags
+                .config
+                .set_shuffle(&config_handler, self.flags.config.shuffle)
+            {
+                log::error!("failed to save shuffle: {}", err);
+            }
+            if let Err(err) = self
+                .flags
+                .config
+                .set_volume(&config_handler, self.flags.config.volume)
+            {
+                log::error!("failed to save volume: {}", err);
+            }
+        }
+        Command::none()
+    }
+
+    /// Swaps in `entry` as the active [`Video`], preserving play state, and
+    /// kicks off a master-playlist fetch if it's an HLS source (mirroring
+    /// the startup path in `init`).
+    fn load_entry(&mut self, entry: &PlaylistEntry) -> Command<Message> {
+        self.variants.clear();
+        self.variant_selection = VariantSelection::Auto;
+        self.current_variant = None;
+        self.bandwidth = BandwidthEstimator::new();
+        self.last_bandwidth_sample = Instant::now();
+        self.stalled_since = None;
+        self.position = 0.0;
+        self.thumbnails = Arc::new(Mutex::new(ThumbnailGenerator::new(entry.url.as_str())));
+        self.metadata = None;
+        self.has_video = true;
+
+        let paused = self.video.paused();
+        if let Ok(video) = Video::new(&entry.url) {
+            self.video = video;
+            self.video.set_paused(paused);
+            self.video
+                .set_volume(if self.muted { 0.0 } else { self.volume });
+            self.wire_bandwidth_probe();
+        }
+
+        let mut commands = vec![
+            Command::perform(metadata::load(entry.url.clone()), Message::MetadataLoaded),
+            Command::perform(has_video_track(entry.url.clone()), Message::VideoTrackProbed),
+        ];
+        if hls::is_hls_url(&entry.url) {
+            let url = entry.url.clone();
+            commands.push(Command::perform(hls::fetch_master_playlist(url), |variants| {
+                Message::VariantsLoaded(variants.unwrap_or_default())
+            }));
+        }
+        Command::batch(commands)
+    }
+
+    /// Mirrors current playback state onto the MPRIS D-Bus properties.
+    fn publish_mpris_state(&self) {
+        mpris::set_state(mpris::PlayerState {
+            playing: !self.video.paused(),
+            position_secs: self.position,
+            duration_secs: self.video.duration().as_secs_f64(),
+            volume: if self.muted { 0.0 } else { self.volume },
+            loop_status: if self.video.looping() {
+                mpris::LoopStatus::Playlist
+            } else {
+                mpris::LoopStatus::None
+            },
+            title: match (&self.metadata, self.playlist.current()) {
+                (Some(metadata), Some(entry)) => metadata.display_title(&entry.url),
+                (None, Some(entry)) => entry.title(),
+                (_, None) => String::new(),
+            },
+        });
+    }
+
+    /// The width, in pixels, the seek slider is rendered at. Fixed so hover
+    /// position can be mapped back to a timestamp.
+    const SLIDER_WIDTH: f32 = 480.0;
+
+    /// The seek slider, wrapped in a [`widget::mouse_area`] that tracks the
+    /// hovered timestamp and shows a filmstrip preview above it.
+    fn seek_slider(&self) -> Element<Message> {
+        let duration = self.video.duration().as_secs_f64();
+        let slider = Slider::new(0.0..=duration, self.position, Message::Seek)
+            .step(0.1)
+            .width(Length::Fixed(Self::SLIDER_WIDTH))
+            .on_release(Message::SeekRelease);
+
+        let area = widget::mouse_area(slider)
+            .on_move(move |point| {
+                let fraction = (point.x / Self::SLIDER_WIDTH).clamp(0.0, 1.0) as f64;
+                Message::HoverSeek(fraction * duration)
+            })
+            .on_exit(Message::HoverEnd);
+
+        match &self.hover {
+            Some((secs, Some(thumbnail))) => widget::column()
+                .push(self.thumbnail_preview(*secs, thumbnail))
+                .push(area)
+                .into(),
+            _ => area.into(),
+        }
+    }
+
+    /// Renders a cached thumbnail as a small overlay above the slider.
+    fn thumbnail_preview<'a>(&self, secs: f64, thumbnail: &Thumbnail) -> Element<'a, Message> {
+        let handle = widget::image::Handle::from_pixels(
+            thumbnail.width,
+            thumbnail.height,
+            thumbnail.rgba.clone(),
+        );
+        widget::column()
+            .push(widget::image(handle).width(Length::Fixed(160.0)))
+            .push(widget::text(format!("{}s", secs as u64)))
+            .into()
+    }
+
+    /// The quality drop-down, when the current media is an HLS stream.
+    fn quality_selector(&self) -> Option<Element<Message>> {
+        if self.variants.is_empty() {
+            return None;
+        }
+
+        let mut labels = vec!["Auto".to_string()];
+        labels.extend(self.variants.iter().map(Variant::label));
+
+        let selected = match self.variant_selection {
+            VariantSelection::Auto => 0,
+            VariantSelection::Fixed(index) => index + 1,
+        };
+
+        Some(
+            widget::dropdown(&labels, Some(selected), Message::SelectVariant)
+                .width(Length::Fixed(120.0))
+                .into(),
+        )
+    }
+
+    /// Mute toggle and volume slider, reflecting the current level.
+    fn volume_controls(&self) -> Element<Message> {
+        let level = if self.muted { 0.0 } else { self.volume };
+        let icon_name = if self.muted || level <= 0.0 {
+            "audio-volume-muted-symbolic"
+        } else if level < 0.5 {
+            "audio-volume-low-symbolic"
+        } else {
+            "audio-volume-high-symbolic"
+        };
+
+        Row::new()
+            .spacing(8)
+            .push(
+                widget::button::icon(widget::icon::from_name(icon_name).size(16))
+                    .on_press(Message::ToggleMute),
+            )
+            .push(
+                Slider::new(0.0..=1.0, level, move |new_level| {
+                    Message::SetVolume(new_level - level)
+                })
+                .step(0.01)
+                .width(Length::Fixed(100.0)),
+            )
+            .into()
+    }
+
+    /// Shown in place of the video widget for audio-only media: embedded
+    /// cover art plus title/artist/album/track tags.
+    fn now_playing_panel(&self) -> Element<Message> {
+        let mut column = Column::new().spacing(8).align_items(cosmic::iced::Alignment::Center);
+
+        if let Some(cover) = self.metadata.as_ref().and_then(|m| m.cover.as_ref()) {
+            let handle = widget::image::Handle::from_pixels(cover.width, cover.height, cover.rgba.clone());
+            column = column.push(widget::image(handle).width(Length::Fixed(320.0)));
+        } else {
+            column = column.push(widget::icon::from_name("audio-x-generic-symbolic").size(128));
+        }
+
+        if let Some(metadata) = &self.metadata {
+            if let Some(title) = &metadata.title {
+                column = column.push(widget::text::title3(title));
+            }
+            let subtitle = [metadata.artist.as_deref(), metadata.album.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" — ");
+            if !subtitle.is_empty() {
+                column = column.push(widget::text(subtitle));
+            }
+            if let Some(track_number) = metadata.track_number {
+                column = column.push(widget::text(format!("Track {track_number}")));
+            }
+        }
+
+        column.into()
+    }
+
+    /// A side panel listing the queue, with the currently-playing entry
+    /// highlighted.
+    fn queue_panel(&self) -> Element<Message> {
+        let mut list = Column::new().spacing(4).width(Length::FillPortion(1));
+        for (index, entry) in self.playlist.entries().iter().enumerate() {
+            let label = widget::text(entry.title());
+            let row = if index == self.playlist.current_index() {
+                widget::button::custom(label).class(widget::button::Style::Suggested)
+            } else {
+                widget::button::custom(label).class(widget::button::Style::Text)
+            };
+            list = list.push(row.on_press(Message::PlayIndex(index)));
+        }
+        widget::scrollable(list).into()
     }
 }
 
@@ -148,7 +604,18 @@ impl Application for App {
 
     /// Creates the application, and optionally emits command on initialize.
     fn init(core: Core, flags: Self::Flags) -> (Self, Command<Self::Message>) {
-        let video = Video::new(&flags.url).unwrap();
+        let playlist = Playlist::new(
+            flags.entries.clone(),
+            flags.config.repeat_mode,
+            flags.config.shuffle,
+            playlist::entropy_seed(),
+        );
+        let current_url = playlist.current().expect("non-empty playlist").url.clone();
+        let is_hls = hls::is_hls_url(&current_url);
+        let mut video = Video::new(&current_url).unwrap();
+        let url = current_url.to_string();
+        let volume = flags.config.volume;
+        video.set_volume(volume);
         let mut app = App {
             core,
             flags,
@@ -156,9 +623,33 @@ impl Application for App {
             video,
             position: 0.0,
             dragging: false,
+            variants: Vec::new(),
+            variant_selection: VariantSelection::Auto,
+            current_variant: None,
+            bandwidth: BandwidthEstimator::new(),
+            bandwidth_bytes: Arc::new(AtomicU64::new(0)),
+            thumbnails: Arc::new(Mutex::new(ThumbnailGenerator::new(&url))),
+            hover: None,
+            playlist,
+            metadata: None,
+            has_video: true,
+            volume,
+            muted: false,
+            last_bandwidth_sample: Instant::now(),
+            stalled_since: None,
         };
-        let command = app.update_title();
-        (app, command)
+        app.wire_bandwidth_probe();
+        let mut commands = vec![
+            app.update_title(),
+            Command::perform(metadata::load(current_url.clone()), Message::MetadataLoaded),
+            Command::perform(has_video_track(current_url.clone()), Message::VideoTrackProbed),
+        ];
+        if is_hls {
+            commands.push(Command::perform(hls::fetch_master_playlist(current_url), |variants| {
+                Message::VariantsLoaded(variants.unwrap_or_default())
+            }));
+        }
+        (app, Command::batch(commands))
     }
 
     /// Handle application events here.
@@ -180,9 +671,11 @@ impl Application for App {
             }
             Message::TogglePause => {
                 self.video.set_paused(!self.video.paused());
+                self.publish_mpris_state();
             }
             Message::ToggleLoop => {
                 self.video.set_looping(!self.video.looping());
+                self.publish_mpris_state();
             }
             Message::Seek(secs) => {
                 self.dragging = true;
@@ -204,38 +697,185 @@ impl Application for App {
                     .expect("seek");
                 self.video.set_paused(false);
             }
+            Message::SeekAbsolute(secs) => {
+                // A one-shot seek (MPRIS `SetPosition`), distinct from
+                // `Seek`: it must not leave `dragging` set, since no
+                // `SeekRelease` follows to clear it.
+                self.dragging = false;
+                self.position = secs.clamp(0.0, self.video.duration().as_secs_f64());
+                self.video
+                    .seek(Duration::from_secs_f64(self.position), true)
+                    .ok();
+                self.publish_mpris_state();
+            }
+            Message::Stop => {
+                self.dragging = false;
+                self.position = 0.0;
+                self.video.seek(Duration::ZERO, true).ok();
+                self.video.set_paused(true);
+                self.publish_mpris_state();
+            }
             Message::EndOfStream => {
-                println!("end of stream");
+                if let Some(entry) = self.playlist.advance().cloned() {
+                    return self.load_entry(&entry);
+                }
             }
             Message::NewFrame => {
                 if self.dragging {
                     self.video.set_paused(true);
                 } else {
+                    let previous_position = self.position;
                     self.position = self.video.position().as_secs_f64();
+                    self.reevaluate_bandwidth(previous_position);
                 }
+                self.publish_mpris_state();
             }
             Message::SystemThemeModeChange(_theme_mode) => {
                 return self.update_config();
             }
+            Message::VariantsLoaded(variants) => {
+                self.variants = variants;
+                if let Some(variant) = self.active_variant().cloned() {
+                    self.load_variant(&variant);
+                }
+            }
+            Message::SelectVariant(index) => {
+                self.variant_selection = if index == 0 {
+                    VariantSelection::Auto
+                } else {
+                    VariantSelection::Fixed(index - 1)
+                };
+                if let Some(variant) = self.active_variant().cloned() {
+                    self.load_variant(&variant);
+                }
+            }
+            Message::HoverSeek(secs) => {
+                if let Some(cached) = self.thumbnails.lock().unwrap().cached(secs) {
+                    self.hover = Some((secs, Some(cached)));
+                    return Command::none();
+                }
+                self.hover = Some((secs, None));
+                let thumbnails = self.thumbnails.clone();
+                return Command::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            // Only the URI lookup and the final cache
+                            // insert touch the mutex; the blocking
+                            // extraction itself runs lock-free so a slow
+                            // frame pull can't stall `update()`'s own
+                            // `cached()` check for later hovers.
+                            let uri = thumbnails.lock().unwrap().uri().to_string();
+                            let thumbnail = thumbnail::extract_frame(&uri, secs as u64)?;
+                            thumbnails.lock().unwrap().insert(secs, thumbnail.clone());
+                            Some(thumbnail)
+                        })
+                        .await
+                        .unwrap_or(None)
+                    },
+                    move |thumbnail| Message::ThumbnailReady(secs, thumbnail),
+                );
+            }
+            Message::HoverEnd => {
+                self.hover = None;
+            }
+            Message::ThumbnailReady(secs, thumbnail) => {
+                if matches!(self.hover, Some((hovered, _)) if hovered == secs) {
+                    self.hover = Some((secs, thumbnail));
+                }
+            }
+            Message::Next => {
+                if let Some(entry) = self.playlist.advance().cloned() {
+                    return self.load_entry(&entry);
+                }
+            }
+            Message::Previous => {
+                if let Some(entry) = self.playlist.previous().cloned() {
+                    return self.load_entry(&entry);
+                }
+            }
+            Message::PlayIndex(index) => {
+                if let Some(entry) = self.playlist.play_index(index).cloned() {
+                    return self.load_entry(&entry);
+                }
+            }
+            Message::ToggleShuffle => {
+                self.playlist.set_shuffle(!self.playlist.shuffle);
+                self.flags.config.shuffle = self.playlist.shuffle;
+                return self.save_config();
+            }
+            Message::CycleRepeat => {
+                self.playlist.repeat_mode = self.playlist.repeat_mode.cycle();
+                self.flags.config.repeat_mode = self.playlist.repeat_mode;
+                return self.save_config();
+            }
+            Message::MetadataLoaded(metadata) => {
+                self.metadata = metadata;
+                return self.update_title();
+            }
+            Message::SetVolume(delta) => {
+                self.volume = (self.volume + delta).clamp(0.0, 1.0);
+                self.muted = false;
+                self.video.set_volume(self.volume);
+                self.flags.config.volume = self.volume;
+                self.publish_mpris_state();
+                return self.save_config();
+            }
+            Message::SetVolumeLevel(level) => {
+                self.volume = level.clamp(0.0, 1.0);
+                self.muted = false;
+                self.video.set_volume(self.volume);
+                self.flags.config.volume = self.volume;
+                self.publish_mpris_state();
+                return self.save_config();
+            }
+            Message::ToggleMute => {
+                self.muted = !self.muted;
+                self.video
+                    .set_volume(if self.muted { 0.0 } else { self.volume });
+                self.publish_mpris_state();
+            }
+            Message::SetLoopStatus(status) => {
+                self.video.set_looping(!matches!(status, mpris::LoopStatus::None));
+                self.publish_mpris_state();
+            }
+            Message::VideoTrackProbed(has_video) => {
+                self.has_video = has_video;
+            }
         }
         Command::none()
     }
 
     /// Creates a view after each update.
     fn view(&self) -> Element<Self::Message> {
-        Column::new()
+        // `VideoPlayer` stays mounted even for audio-only media: it's what
+        // drives `on_new_frame`/`on_end_of_stream`, just sized down in
+        // favor of the now-playing panel below.
+        let is_audio_only = self.is_audio_only();
+        let video_player = VideoPlayer::new(&self.video)
+            .on_end_of_stream(Message::EndOfStream)
+            .on_new_frame(Message::NewFrame)
+            .width(Length::Fill)
+            .height(if is_audio_only {
+                Length::Fixed(0.0)
+            } else {
+                Length::Fill
+            });
+
+        let player = Column::new()
             .push(widget::vertical_space(Length::Fill))
-            .push(
-                VideoPlayer::new(&self.video)
-                    .on_end_of_stream(Message::EndOfStream)
-                    .on_new_frame(Message::NewFrame)
-                    .width(Length::Fill),
-            )
+            .push(video_player)
+            .push_maybe(is_audio_only.then(|| self.now_playing_panel()))
             .push(widget::vertical_space(Length::Fill))
             .push(
                 Row::new()
                     .height(Length::Fixed(16.0))
                     .spacing(8)
+                    .push(
+                        widget::button::icon(widget::icon::from_name(
+                            "media-skip-backward-symbolic",
+                        ))
+                        .on_press(Message::Previous),
+                    )
                     .push(
                         widget::button::icon(if self.video.paused() {
                             widget::icon::from_name("media-playback-start-symbolic").size(16)
@@ -244,24 +884,43 @@ impl Application for App {
                         })
                         .on_press(Message::TogglePause),
                     )
+                    .push(
+                        widget::button::icon(widget::icon::from_name(
+                            "media-skip-forward-symbolic",
+                        ))
+                        .on_press(Message::Next),
+                    )
                     .push(widget::text(format!(
                         "{:#?}s / {:#?}s",
                         self.position as u64,
                         self.video.duration().as_secs()
                     )))
+                    .push(self.seek_slider())
+                    .push_maybe(self.quality_selector())
                     .push(
-                        Slider::new(
-                            0.0..=self.video.duration().as_secs_f64(),
-                            self.position,
-                            Message::Seek,
-                        )
-                        .step(0.1)
-                        .on_release(Message::SeekRelease),
-                    ),
-            )
+                        widget::button::icon(widget::icon::from_name(
+                            "media-playlist-shuffle-symbolic",
+                        ))
+                        .on_press(Message::ToggleShuffle),
+                    )
+                    .push(
+                        widget::button::text(match self.playlist.repeat_mode {
+                            RepeatMode::Off => "Repeat: Off",
+                            RepeatMode::All => "Repeat: All",
+                            RepeatMode::One => "Repeat: One",
+                        })
+                        .on_press(Message::CycleRepeat),
+                    )
+                    .push(self.volume_controls()),
+            );
+
+        Row::new()
+            .push(player.width(Length::FillPortion(3)))
+            .push(self.queue_panel())
             .into()
     }
 
+
     fn subscription(&self) -> Subscription<Self::Message> {
         struct ConfigSubscription;
         struct ThemeSubscription;
@@ -295,6 +954,7 @@ impl Application for App {
                 }
                 Message::SystemThemeModeChange(update.config)
             }),
+            mpris::subscription(),
         ])
     }
 }
@@ -0,0 +1,250 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Parsing and adaptive selection for HLS master playlists.
+//!
+//! This module only concerns itself with the *master* playlist: the list of
+//! variant streams a server advertises and which one we should be playing
+//! right now. Media-playlist (segment) parsing is left to GStreamer's own
+//! `hlsdemux`; we only need enough information up front to populate a
+//! quality menu and to feed a bandwidth estimate back into variant choice.
+
+use gst::prelude::*;
+use std::time::Instant;
+
+/// A single `EXT-X-STREAM-INF` entry from a master playlist.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variant {
+    pub uri: url::Url,
+    pub bandwidth: u64,
+    pub resolution: Option<(u32, u32)>,
+    pub codecs: Vec<String>,
+}
+
+impl Variant {
+    /// A short label suitable for the quality drop-down, e.g. `"1080p"` or
+    /// falling back to the bandwidth when no resolution was advertised.
+    pub fn label(&self) -> String {
+        match self.resolution {
+            Some((_, height)) => format!("{height}p"),
+            None => format!("{} kb/s", self.bandwidth / 1000),
+        }
+    }
+}
+
+/// Returns `true` if `url`'s path looks like an HLS master playlist.
+pub fn is_hls_url(url: &url::Url) -> bool {
+    url.path().ends_with(".m3u8")
+}
+
+/// Parses a master playlist, keeping only variants whose codecs GStreamer
+/// can actually decode on this machine.
+pub fn parse_master_playlist(base: &url::Url, text: &str) -> Vec<Variant> {
+    let mut variants = Vec::new();
+    let mut pending: Option<(u64, Option<(u32, u32)>, Vec<String>)> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(attrs) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+            let bandwidth = attr(attrs, "BANDWIDTH")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            let resolution = attr(attrs, "RESOLUTION").and_then(|v| {
+                let (w, h) = v.split_once('x')?;
+                Some((w.parse().ok()?, h.parse().ok()?))
+            });
+            let codecs = attr(attrs, "CODECS")
+                .map(|v| v.trim_matches('"').split(',').map(str::to_string).collect())
+                .unwrap_or_default();
+            pending = Some((bandwidth, resolution, codecs));
+        } else if !line.is_empty() && !line.starts_with('#') {
+            if let Some((bandwidth, resolution, codecs)) = pending.take() {
+                if let Ok(uri) = base.join(line) {
+                    variants.push(Variant {
+                        uri,
+                        bandwidth,
+                        resolution,
+                        codecs,
+                    });
+                }
+            }
+        }
+    }
+
+    variants.retain(|variant| variant.codecs.iter().all(|codec| codec_is_decodable(codec)));
+    variants.sort_by_key(|variant| variant.bandwidth);
+    variants
+}
+
+/// Extracts a quoted-or-bare attribute value out of an `EXT-X-STREAM-INF`
+/// attribute list.
+fn attr<'a>(attrs: &'a str, key: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    while !rest.is_empty() {
+        let (name, after) = rest.split_once('=')?;
+        // Quoted values (e.g. `CODECS="avc1.640028,mp4a.40.2"`) may contain
+        // commas, so they run to the closing quote rather than the next comma.
+        let (value, next) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"')?;
+                (&quoted[..end], quoted[end + 1..].trim_start_matches(','))
+            }
+            None => after.split_once(',').unwrap_or((after, "")),
+        };
+        if name.trim() == key {
+            return Some(value.trim());
+        }
+        rest = next;
+    }
+    None
+}
+
+/// Checks the GStreamer registry for a decoder matching `codec` (an RFC 6381
+/// codec string, e.g. `avc1.640028` or `mp4a.40.2`).
+fn codec_is_decodable(codec: &str) -> bool {
+    let Some(caps) = caps_for_codec(codec) else {
+        // Unknown codec strings are allowed through; we'd rather attempt
+        // playback than hide a variant we misparsed.
+        return true;
+    };
+    gst::Registry::get()
+        .feature_filter(
+            |feature| {
+                let Some(factory) = feature.downcast_ref::<gst::ElementFactory>() else {
+                    return false;
+                };
+                factory.klass().contains("Decoder") && factory.can_sink_all_caps(&caps)
+            },
+            false,
+        )
+        .first()
+        .is_some()
+}
+
+fn caps_for_codec(codec: &str) -> Option<gst::Caps> {
+    let family = codec.split('.').next()?;
+    let media_type = match family {
+        "avc1" | "avc3" => "video/x-h264",
+        "hvc1" | "hev1" => "video/x-h265",
+        "vp09" => "video/x-vp9",
+        "av01" => "video/x-av1",
+        "mp4a" => "audio/mpeg",
+        "opus" => "audio/x-opus",
+        _ => return None,
+    };
+    Some(gst::Caps::builder(media_type).build())
+}
+
+/// Smoothed throughput estimate used to drive automatic variant selection.
+///
+/// Each completed segment download reports its observed throughput, which
+/// is folded into an exponentially-weighted moving average. Switching down
+/// happens immediately on starvation (a stalled/slow segment); switching up
+/// requires `STABLE_SAMPLES` consecutive samples that would justify the
+/// higher variant, so a single lucky fast segment doesn't cause flapping.
+#[derive(Debug)]
+pub struct BandwidthEstimator {
+    /// Smoothing factor in `(0, 1]`; higher weighs recent samples more.
+    alpha: f64,
+    estimate_bps: Option<f64>,
+    stable_samples: u32,
+    last_sample_at: Option<Instant>,
+}
+
+impl BandwidthEstimator {
+    /// Only switch to a higher variant once its bandwidth requirement has
+    /// been comfortably satisfied for this many consecutive segments.
+    const STABLE_SAMPLES: u32 = 3;
+
+    pub fn new() -> Self {
+        Self {
+            alpha: 0.3,
+            estimate_bps: None,
+            stable_samples: 0,
+            last_sample_at: None,
+        }
+    }
+
+    /// Records that `bytes` were downloaded in `elapsed`.
+    pub fn record_segment(&mut self, bytes: u64, elapsed: std::time::Duration) {
+        if elapsed.as_secs_f64() <= 0.0 {
+            return;
+        }
+        let sample_bps = bytes as f64 * 8.0 / elapsed.as_secs_f64();
+        self.estimate_bps = Some(match self.estimate_bps {
+            Some(prev) => self.alpha * sample_bps + (1.0 - self.alpha) * prev,
+            None => sample_bps,
+        });
+        self.last_sample_at = Some(Instant::now());
+    }
+
+    /// Picks the highest variant whose bandwidth is below
+    /// `safety_factor * estimate`, applying hysteresis against the
+    /// previously selected variant.
+    pub fn select<'a>(
+        &mut self,
+        variants: &'a [Variant],
+        current: Option<&'a Variant>,
+        safety_factor: f64,
+    ) -> Option<&'a Variant> {
+        let Some(estimate) = self.estimate_bps else {
+            return variants.first();
+        };
+
+        let budget = estimate * safety_factor;
+        let affordable = variants
+            .iter()
+            .filter(|v| v.bandwidth as f64 <= budget)
+            .max_by_key(|v| v.bandwidth)
+            .or_else(|| variants.first());
+
+        match (current, affordable) {
+            (Some(current), Some(candidate)) if candidate.bandwidth > current.bandwidth => {
+                self.stable_samples += 1;
+                if self.stable_samples >= Self::STABLE_SAMPLES {
+                    self.stable_samples = 0;
+                    Some(candidate)
+                } else {
+                    Some(current)
+                }
+            }
+            (_, candidate) => {
+                self.stable_samples = 0;
+                candidate
+            }
+        }
+    }
+
+    /// Reacts to segment starvation by halving the estimate and clearing the
+    /// hysteresis count, so the next `select` switches down at once if the
+    /// current variant no longer fits and ramps back up cautiously.
+    pub fn note_starvation(&mut self) {
+        self.estimate_bps = self.estimate_bps.map(|e| e * 0.5);
+        self.stable_samples = 0;
+    }
+}
+
+impl Default for BandwidthEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Downloads and parses a master playlist, returning `None` on any network
+/// or parse failure so the caller can fall back to playing `url` directly.
+pub async fn fetch_master_playlist(url: url::Url) -> Option<Vec<Variant>> {
+    let text = reqwest::get(url.clone()).await.ok()?.text().await.ok()?;
+    let variants = parse_master_playlist(&url, &text);
+    if variants.is_empty() {
+        None
+    } else {
+        Some(variants)
+    }
+}
+
+/// Which variant drives playback: a user-pinned quality, or the estimator.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VariantSelection {
+    Auto,
+    Fixed(usize),
+}
@@ -0,0 +1,50 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::keyboard::{key, Key, Modifiers};
+use std::collections::HashMap;
+
+use crate::gstreamer::Action;
+
+/// A keyboard shortcut: a key plus the modifiers that must be held with it.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct KeyBind {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl KeyBind {
+    pub fn matches(&self, modifiers: Modifiers, key: &Key) -> bool {
+        self.key == *key && self.modifiers == modifiers
+    }
+}
+
+/// Builds the default key bindings.
+pub fn key_binds() -> HashMap<KeyBind, Action> {
+    let mut key_binds = HashMap::new();
+
+    macro_rules! bind {
+        ([$($modifier:ident),* $(,)?], $key:expr, $action:ident) => {{
+            key_binds.insert(
+                KeyBind {
+                    modifiers: Modifiers::empty() $(| Modifiers::$modifier)*,
+                    key: $key,
+                },
+                Action::$action,
+            );
+        }};
+    }
+
+    bind!([], Key::Named(key::Named::ArrowLeft), SeekBackward);
+    bind!([], Key::Named(key::Named::ArrowRight), SeekForward);
+    bind!([], Key::Named(key::Named::MediaTrackNext), Next);
+    bind!([], Key::Named(key::Named::MediaTrackPrevious), Previous);
+    bind!([CTRL], Key::Named(key::Named::ArrowRight), Next);
+    bind!([CTRL], Key::Named(key::Named::ArrowLeft), Previous);
+    bind!([], Key::Named(key::Named::ArrowUp), VolumeUp);
+    bind!([], Key::Named(key::Named::ArrowDown), VolumeDown);
+    bind!([], Key::Named(key::Named::AudioVolumeMute), ToggleMute);
+    bind!([], Key::Character("m".into()), ToggleMute);
+
+    key_binds
+}
@@ -0,0 +1,77 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Tag metadata and cover art for audio-only media, read via `lofty`.
+//!
+//! This generalizes the player to audio: when a [`Video`](iced_video_player::Video)
+//! has no video stream, `App` shows a "now playing" panel built from a
+//! [`Metadata`] instead of the blank video widget.
+
+use std::path::Path;
+
+/// Tag metadata plus a decoded cover image, shown in the now-playing panel.
+#[derive(Clone, Debug, Default)]
+pub struct Metadata {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track_number: Option<u32>,
+    pub cover: Option<Cover>,
+}
+
+/// A decoded embedded picture, ready to hand to `cosmic::widget::image`.
+#[derive(Clone, Debug)]
+pub struct Cover {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Metadata {
+    /// A title suitable for the window/header title, falling back to the
+    /// file name when no tag is present.
+    pub fn display_title(&self, url: &url::Url) -> String {
+        self.title.clone().unwrap_or_else(|| {
+            url.path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .unwrap_or(url.as_str())
+                .to_string()
+        })
+    }
+}
+
+/// Reads tags and any embedded picture from the local file at `url`.
+/// Returns `None` for remote sources or files `lofty` can't parse.
+pub async fn load(url: url::Url) -> Option<Metadata> {
+    let path = url.to_file_path().ok()?;
+    tokio::task::spawn_blocking(move || read_tags(&path))
+        .await
+        .ok()
+        .flatten()
+}
+
+fn read_tags(path: &Path) -> Option<Metadata> {
+    use lofty::{Accessor, AudioFile, TaggedFileExt};
+
+    let tagged_file = lofty::read_from_path(path).ok()?;
+    let tag = tagged_file.primary_tag().or_else(|| tagged_file.first_tag())?;
+
+    let cover = tag.pictures().first().and_then(decode_cover);
+
+    Some(Metadata {
+        title: tag.title().map(|s| s.to_string()),
+        artist: tag.artist().map(|s| s.to_string()),
+        album: tag.album().map(|s| s.to_string()),
+        track_number: tag.track(),
+        cover,
+    })
+}
+
+fn decode_cover(picture: &lofty::Picture) -> Option<Cover> {
+    let image = image::load_from_memory(picture.data()).ok()?.to_rgba8();
+    Some(Cover {
+        width: image.width(),
+        height: image.height(),
+        rgba: image.into_raw(),
+    })
+}
@@ -0,0 +1,237 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! `org.mpris.MediaPlayer2` D-Bus support, so COSMIC's media keys and
+//! external controllers like `playerctl` can drive playback.
+//!
+//! The D-Bus server runs as an iced [`Subscription`], analogous to how
+//! `ThemeSubscription` injects config updates into the app: incoming method
+//! calls are forwarded out of the subscription as [`Message`]s, and
+//! [`set_state`] lets `App::update` push property changes back in so
+//! external controllers stay in sync.
+
+use cosmic::iced::{
+    futures::SinkExt,
+    subscription::{self, Subscription},
+};
+use std::{any::TypeId, collections::HashMap, sync::OnceLock};
+use tokio::sync::{mpsc, Mutex};
+use zbus::{dbus_interface, zvariant, ConnectionBuilder};
+
+use crate::gstreamer::Message;
+
+const BUS_NAME: &str = "org.mpris.MediaPlayer2.CosmicPlayer";
+const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+
+/// Sender for [`set_state`], populated when the MPRIS subscription starts,
+/// before its D-Bus server is built.
+static STATE_TX: OnceLock<mpsc::UnboundedSender<PlayerState>> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LoopStatus {
+    #[default]
+    None,
+    Track,
+    Playlist,
+}
+
+impl LoopStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Track => "Track",
+            Self::Playlist => "Playlist",
+        }
+    }
+}
+
+/// Playback state mirrored onto the `Player` D-Bus properties.
+#[derive(Clone, Debug, Default)]
+pub struct PlayerState {
+    pub playing: bool,
+    pub position_secs: f64,
+    pub duration_secs: f64,
+    pub volume: f64,
+    pub loop_status: LoopStatus,
+    pub title: String,
+}
+
+/// Publishes `state` to the MPRIS server and emits `PropertiesChanged`, if
+/// the server has started. Called from `App::update` whenever pause, loop,
+/// or position changes.
+pub fn set_state(state: PlayerState) {
+    if let Some(tx) = STATE_TX.get() {
+        let _ = tx.send(state);
+    }
+}
+
+struct Player {
+    state: Mutex<PlayerState>,
+    commands: mpsc::UnboundedSender<Message>,
+}
+
+#[dbus_interface(name = "org.mpris.MediaPlayer2")]
+impl Player {
+    #[dbus_interface(property)]
+    fn can_quit(&self) -> bool {
+        false
+    }
+
+    #[dbus_interface(property)]
+    fn identity(&self) -> &str {
+        "COSMIC Media Player"
+    }
+}
+
+#[dbus_interface(name = "org.mpris.MediaPlayer2.Player")]
+impl Player {
+    fn play_pause(&self) {
+        let _ = self.commands.send(Message::TogglePause);
+    }
+
+    fn next(&self) {
+        let _ = self.commands.send(Message::Next);
+    }
+
+    fn previous(&self) {
+        let _ = self.commands.send(Message::Previous);
+    }
+
+    fn stop(&self) {
+        let _ = self.commands.send(Message::Stop);
+    }
+
+    fn seek(&self, offset_us: i64) {
+        let _ = self
+            .commands
+            .send(Message::SeekRelative(offset_us as f64 / 1_000_000.0));
+    }
+
+    fn set_position(&self, _track_id: zvariant::ObjectPath<'_>, position_us: i64) {
+        let _ = self
+            .commands
+            .send(Message::SeekAbsolute(position_us as f64 / 1_000_000.0));
+    }
+
+    #[dbus_interface(property)]
+    async fn playback_status(&self) -> &'static str {
+        if self.state.lock().await.playing {
+            "Playing"
+        } else {
+            "Paused"
+        }
+    }
+
+    #[dbus_interface(property)]
+    async fn loop_status(&self) -> String {
+        self.state.lock().await.loop_status.as_str().to_string()
+    }
+
+    #[dbus_interface(property)]
+    async fn set_loop_status(&self, value: String) {
+        let status = match value.as_str() {
+            "Track" => LoopStatus::Track,
+            "Playlist" => LoopStatus::Playlist,
+            _ => LoopStatus::None,
+        };
+        let _ = self.commands.send(Message::SetLoopStatus(status));
+    }
+
+    #[dbus_interface(property)]
+    async fn rate(&self) -> f64 {
+        1.0
+    }
+
+    #[dbus_interface(property)]
+    async fn volume(&self) -> f64 {
+        self.state.lock().await.volume
+    }
+
+    #[dbus_interface(property)]
+    async fn set_volume(&self, value: f64) {
+        let _ = self.commands.send(Message::SetVolumeLevel(value.clamp(0.0, 1.0)));
+    }
+
+    #[dbus_interface(property)]
+    async fn position(&self) -> i64 {
+        (self.state.lock().await.position_secs * 1_000_000.0) as i64
+    }
+
+    #[dbus_interface(property)]
+    async fn metadata(&self) -> HashMap<String, zvariant::Value> {
+        let state = self.state.lock().await;
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "mpris:length".to_string(),
+            zvariant::Value::from((state.duration_secs * 1_000_000.0) as i64),
+        );
+        metadata.insert(
+            "xesam:title".to_string(),
+            zvariant::Value::from(state.title.clone()),
+        );
+        metadata
+    }
+}
+
+/// Starts the MPRIS D-Bus server and returns a [`Subscription`] that
+/// forwards remote commands in as [`Message`]s.
+pub fn subscription() -> Subscription<Message> {
+    struct MprisSubscription;
+
+    subscription::channel(TypeId::of::<MprisSubscription>(), 16, |mut output| async move {
+        let (commands_tx, mut commands_rx) = mpsc::unbounded_channel();
+        let (state_tx, mut state_rx) = mpsc::unbounded_channel();
+        let _ = STATE_TX.set(state_tx);
+
+        let player = Player {
+            state: Mutex::new(PlayerState::default()),
+            commands: commands_tx,
+        };
+
+        let connection = ConnectionBuilder::session()
+            .ok()
+            .and_then(|b| b.name(BUS_NAME).ok())
+            .and_then(|b| b.serve_at(OBJECT_PATH, player).ok());
+
+        let connection = match connection {
+            Some(builder) => match builder.build().await {
+                Ok(connection) => Some(connection),
+                Err(err) => {
+                    log::warn!("failed to start MPRIS D-Bus server: {err}");
+                    None
+                }
+            },
+            None => {
+                log::warn!("failed to configure MPRIS D-Bus server");
+                None
+            }
+        };
+
+        if let Some(connection) = &connection {
+            let connection = connection.clone();
+            tokio::spawn(async move {
+                while let Some(state) = state_rx.recv().await {
+                    if let Ok(iface_ref) = connection
+                        .object_server()
+                        .interface::<_, Player>(OBJECT_PATH)
+                        .await
+                    {
+                        *iface_ref.get().await.state.lock().await = state;
+                        let ctx = iface_ref.signal_context();
+                        let iface = iface_ref.get_mut().await;
+                        let _ = iface.playback_status_changed(ctx).await;
+                        let _ = iface.metadata_changed(ctx).await;
+                        let _ = iface.loop_status_changed(ctx).await;
+                        let _ = iface.volume_changed(ctx).await;
+                    }
+                }
+            });
+        }
+
+        loop {
+            if let Some(message) = commands_rx.recv().await {
+                let _ = output.send(message).await;
+            }
+        }
+    })
+}
@@ -0,0 +1,237 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Playlist state: the queue of media to play, the current position in it,
+//! and the repeat/shuffle modes that decide what plays next.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Extensions treated as playable media when enumerating a directory.
+const MEDIA_EXTENSIONS: &[&str] = &[
+    "mp4", "mkv", "webm", "avi", "mov", "mp3", "flac", "ogg", "wav", "m4a", "opus",
+];
+
+/// One item in the queue.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaylistEntry {
+    pub url: url::Url,
+}
+
+impl PlaylistEntry {
+    pub fn title(&self) -> String {
+        self.url
+            .path_segments()
+            .and_then(|segments| segments.last())
+            .unwrap_or(self.url.as_str())
+            .to_string()
+    }
+}
+
+/// How the queue behaves once it reaches its end.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RepeatMode {
+    #[default]
+    Off,
+    All,
+    One,
+}
+
+impl RepeatMode {
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::Off => Self::All,
+            Self::All => Self::One,
+            Self::One => Self::Off,
+        }
+    }
+}
+
+/// The queue of media to play, plus playback order state.
+pub struct Playlist {
+    entries: Vec<PlaylistEntry>,
+    current: usize,
+    pub repeat_mode: RepeatMode,
+    pub shuffle: bool,
+    /// Precomputed shuffled visit order, consumed as entries are played so
+    /// none repeats until the cycle completes.
+    shuffle_order: Vec<usize>,
+    /// LCG state driving `reshuffle`, seeded once from real entropy (see
+    /// [`entropy_seed`]) and advanced on every call, so replaying the queue
+    /// doesn't produce the same "shuffled" order every time.
+    rng: u64,
+}
+
+impl Playlist {
+    pub fn new(entries: Vec<PlaylistEntry>, repeat_mode: RepeatMode, shuffle: bool, seed: u64) -> Self {
+        let mut playlist = Self {
+            entries,
+            current: 0,
+            repeat_mode,
+            shuffle,
+            shuffle_order: Vec::new(),
+            rng: seed ^ 0x9E3779B97F4A7C15,
+        };
+        if shuffle {
+            playlist.reshuffle();
+        }
+        playlist
+    }
+
+    pub fn entries(&self) -> &[PlaylistEntry] {
+        &self.entries
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn current(&self) -> Option<&PlaylistEntry> {
+        self.entries.get(self.current)
+    }
+
+    pub fn play_index(&mut self, index: usize) -> Option<&PlaylistEntry> {
+        if index >= self.entries.len() {
+            return None;
+        }
+        self.current = index;
+        if self.shuffle {
+            self.shuffle_order.retain(|&i| i != index);
+        }
+        self.entries.get(index)
+    }
+
+    /// Advances to the next entry per the current repeat/shuffle mode.
+    /// Returns `None` when playback should stop (end of a non-repeating
+    /// queue).
+    pub fn advance(&mut self) -> Option<&PlaylistEntry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        if matches!(self.repeat_mode, RepeatMode::One) {
+            return self.entries.get(self.current);
+        }
+
+        let next = if self.shuffle {
+            if self.shuffle_order.is_empty() {
+                if matches!(self.repeat_mode, RepeatMode::Off) {
+                    return None;
+                }
+                self.reshuffle();
+            }
+            self.shuffle_order.pop()
+        } else if self.current + 1 < self.entries.len() {
+            Some(self.current + 1)
+        } else if matches!(self.repeat_mode, RepeatMode::All) {
+            Some(0)
+        } else {
+            None
+        };
+
+        let next = next?;
+        self.current = next;
+        self.entries.get(next)
+    }
+
+    /// Moves to the previous entry, wrapping per repeat mode.
+    pub fn previous(&mut self) -> Option<&PlaylistEntry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let previous = if self.current > 0 {
+            self.current - 1
+        } else if matches!(self.repeat_mode, RepeatMode::All) {
+            self.entries.len() - 1
+        } else {
+            0
+        };
+        self.current = previous;
+        self.entries.get(previous)
+    }
+
+    pub fn set_shuffle(&mut self, shuffle: bool) {
+        self.shuffle = shuffle;
+        if shuffle {
+            self.reshuffle();
+        } else {
+            self.shuffle_order.clear();
+        }
+    }
+
+    /// Recomputes the shuffled visit order, excluding the entry currently
+    /// playing so it isn't immediately repeated.
+    fn reshuffle(&mut self) {
+        let mut order: Vec<usize> = (0..self.entries.len())
+            .filter(|&i| i != self.current)
+            .collect();
+        // A lightweight shuffle: fine for queue sizes typical of a media
+        // player, and avoids pulling in a full `rand` dependency for a
+        // single Fisher-Yates pass. `self.rng` carries state across calls
+        // so the order actually changes each time the queue is reshuffled.
+        for i in (1..order.len()).rev() {
+            self.rng = self.rng.wrapping_mul(6364136223846793005).wrapping_add(1);
+            let j = (self.rng >> 33) as usize % (i + 1);
+            order.swap(i, j);
+        }
+        self.shuffle_order = order;
+    }
+}
+
+/// A one-time seed for `Playlist`'s shuffle RNG, drawn from the system
+/// clock. Not cryptographic, just enough real entropy that shuffled order
+/// differs between launches instead of being fixed by queue length.
+pub fn entropy_seed() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or(0x2545F4914F6CDD1D)
+}
+
+/// Enumerates playable media from CLI arguments: each argument is either a
+/// single file or a directory to scan (non-recursively) for known
+/// extensions.
+pub fn entries_from_args(args: impl Iterator<Item = String>) -> Vec<PlaylistEntry> {
+    let mut entries = Vec::new();
+    for arg in args {
+        let path = Path::new(&arg);
+        if path.is_dir() {
+            let Ok(read_dir) = std::fs::read_dir(path) else {
+                continue;
+            };
+            let mut paths: Vec<_> = read_dir
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| is_media_path(path))
+                .collect();
+            paths.sort();
+            entries.extend(
+                paths
+                    .into_iter()
+                    .filter_map(|path| url_for_path(&path))
+                    .map(|url| PlaylistEntry { url }),
+            );
+        } else if let Some(url) = url::Url::parse(&arg)
+            .ok()
+            .or_else(|| url_for_path(path))
+        {
+            entries.push(PlaylistEntry { url });
+        }
+    }
+    entries
+}
+
+fn is_media_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| MEDIA_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+fn url_for_path(path: &Path) -> Option<url::Url> {
+    // `from_file_path` only accepts absolute paths.
+    let path = std::fs::canonicalize(path).ok()?;
+    url::Url::from_file_path(path).ok()
+}
@@ -0,0 +1,164 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Filmstrip-style thumbnail previews for the seek slider.
+//!
+//! Thumbnails are produced by a second, disposable GStreamer pipeline built
+//! purely for still extraction, rather than reusing the main playback
+//! [`Video`](iced_video_player::Video). This mirrors the approach used by
+//! media-preview generators: decode just enough to grab one frame near the
+//! requested timestamp, then throw the pipeline's decoded state away.
+
+use gst::prelude::*;
+use std::collections::HashMap;
+
+/// Thumbnails are generated and cached at a coarse time resolution so that
+/// hovering back and forth over the same few seconds of the slider is
+/// served from cache instead of round-tripping through GStreamer.
+const BUCKET_SECS: u64 = 5;
+
+/// Width, in pixels, that extracted frames are downscaled to.
+const THUMBNAIL_WIDTH: u32 = 160;
+
+/// Maximum number of cached thumbnails before the least-recently-used
+/// entries are evicted.
+const CACHE_CAPACITY: usize = 64;
+
+/// A downscaled RGBA still, ready to hand to `cosmic::widget::image`.
+#[derive(Clone)]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// Rounds `secs` down to its cache bucket.
+fn bucket(secs: f64) -> u64 {
+    (secs as u64 / BUCKET_SECS) * BUCKET_SECS
+}
+
+/// Least-recently-used cache of generated thumbnails, keyed by time bucket.
+struct Lru {
+    capacity: usize,
+    entries: HashMap<u64, Thumbnail>,
+    order: Vec<u64>,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    fn get(&mut self, key: u64) -> Option<Thumbnail> {
+        if self.entries.contains_key(&key) {
+            self.touch(key);
+        }
+        self.entries.get(&key).cloned()
+    }
+
+    fn insert(&mut self, key: u64, thumbnail: Thumbnail) {
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            if let Some(oldest) = (!self.order.is_empty()).then(|| self.order.remove(0)) {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, thumbnail);
+        self.touch(key);
+    }
+
+    fn touch(&mut self, key: u64) {
+        self.order.retain(|&k| k != key);
+        self.order.push(key);
+    }
+}
+
+/// Holds the `uri` preview frames are extracted from (see [`extract_frame`])
+/// and caches the results so repeated hovers over the same bucket are
+/// instant.
+pub struct ThumbnailGenerator {
+    uri: String,
+    cache: Lru,
+}
+
+impl ThumbnailGenerator {
+    pub fn new(uri: &str) -> Self {
+        Self {
+            uri: uri.to_string(),
+            cache: Lru::new(CACHE_CAPACITY),
+        }
+    }
+
+    /// Returns a cached thumbnail for `position` if one has already been
+    /// generated, without blocking on a fresh extraction.
+    pub fn cached(&mut self, position: f64) -> Option<Thumbnail> {
+        self.cache.get(bucket(position))
+    }
+
+    /// The URI thumbnails are generated from, for callers that need to
+    /// extract a fresh one (see [`extract_frame`]) without holding the
+    /// generator locked for the extraction itself.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Caches a thumbnail extracted via [`extract_frame`] for `position`.
+    pub fn insert(&mut self, position: f64, thumbnail: Thumbnail) {
+        self.cache.insert(bucket(position), thumbnail);
+    }
+}
+
+/// Builds a throwaway `playbin3 ! appsink` pipeline, seeks to the keyframe
+/// nearest `secs`, and pulls a single downscaled RGBA frame.
+///
+/// Blocks the calling thread on pipeline state changes and a single
+/// `appsink` pull, so callers must run this off the UI thread (e.g. via
+/// `Command::perform` on a blocking executor) and without holding any lock
+/// a hover update would need, so a slow extraction can't stall `update()`.
+pub fn extract_frame(uri: &str, secs: u64) -> Option<Thumbnail> {
+    let pipeline = gst::ElementFactory::make("playbin3")
+        .property("uri", uri)
+        .build()
+        .ok()?;
+
+    let caps = gst::Caps::builder("video/x-raw")
+        .field("format", "RGBA")
+        .field("width", THUMBNAIL_WIDTH as i32)
+        .build();
+    let sink = gst_app::AppSink::builder()
+        .caps(&caps)
+        .max_buffers(1)
+        .drop(true)
+        .build();
+    pipeline.set_property("video-sink", &sink);
+
+    pipeline.set_state(gst::State::Paused).ok()?;
+    pipeline
+        .state(gst::ClockTime::from_seconds(5))
+        .0
+        .ok()?;
+
+    pipeline
+        .seek_simple(
+            gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT,
+            gst::ClockTime::from_seconds(secs),
+        )
+        .ok()?;
+
+    let sample = sink.pull_preroll().or_else(|_| sink.pull_sample()).ok()?;
+    let buffer = sample.buffer()?;
+    let info = gst_video::VideoInfo::from_caps(sample.caps()?).ok()?;
+    let map = buffer.map_readable().ok()?;
+
+    let thumbnail = Thumbnail {
+        width: info.width(),
+        height: info.height(),
+        rgba: map.as_slice().to_vec(),
+    };
+
+    pipeline.set_state(gst::State::Null).ok()?;
+    Some(thumbnail)
+}